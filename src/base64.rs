@@ -0,0 +1,186 @@
+use std::fmt;
+
+use crate::Error;
+
+const ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const PAD: u8 = b'=';
+
+/// The standard RFC 2045 line length: a CRLF is inserted every 76 output
+/// characters so the text stays safe for tooling that wraps long lines.
+pub const DEFAULT_LINE_WIDTH: usize = 76;
+
+/// Encodes `input` as standard base64, inserting a CRLF every
+/// `line_width` output characters. Works through the input in 3-byte
+/// groups, appending to one output `String` whose capacity is reserved
+/// up front; the whole encoded text is built in memory and returned at
+/// once.
+pub fn encode(input: &[u8], line_width: usize) -> String {
+    let mut out = String::with_capacity(input.len() * 4 / 3 + 4);
+    let mut line_len = 0;
+
+    let push = |c: char, out: &mut String, line_len: &mut usize| {
+        if line_width > 0 && *line_len == line_width {
+            out.push_str("\r\n");
+            *line_len = 0;
+        }
+        out.push(c);
+        *line_len += 1;
+    };
+
+    for group in input.chunks(3) {
+        let b0 = group[0];
+        let b1 = *group.get(1).unwrap_or(&0);
+        let b2 = *group.get(2).unwrap_or(&0);
+
+        let c0 = b0 >> 2;
+        let c1 = ((b0 & 0x03) << 4) | (b1 >> 4);
+        let c2 = ((b1 & 0x0f) << 2) | (b2 >> 6);
+        let c3 = b2 & 0x3f;
+
+        push(ALPHABET[c0 as usize] as char, &mut out, &mut line_len);
+        push(ALPHABET[c1 as usize] as char, &mut out, &mut line_len);
+        push(
+            if group.len() > 1 {
+                ALPHABET[c2 as usize] as char
+            } else {
+                PAD as char
+            },
+            &mut out,
+            &mut line_len,
+        );
+        push(
+            if group.len() > 2 {
+                ALPHABET[c3 as usize] as char
+            } else {
+                PAD as char
+            },
+            &mut out,
+            &mut line_len,
+        );
+    }
+
+    out
+}
+
+/// Decodes standard base64 text, tolerating and stripping any embedded
+/// whitespace or newlines (such as the CRLFs [`encode`] inserts).
+pub fn decode(input: &str) -> crate::Result<Vec<u8>> {
+    let filtered: Vec<u8> = input
+        .bytes()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+
+    if !filtered.len().is_multiple_of(4) {
+        return Err(Box::new(Base64Error::InvalidLength(filtered.len())));
+    }
+
+    let mut out = Vec::with_capacity(filtered.len() / 4 * 3);
+
+    for group in filtered.chunks(4) {
+        let mut values = [0u8; 4];
+        let mut pad_count = 0;
+
+        for (i, &b) in group.iter().enumerate() {
+            if b == PAD {
+                pad_count += 1;
+                values[i] = 0;
+            } else {
+                values[i] = decode_char(b)?;
+            }
+        }
+
+        out.push((values[0] << 2) | (values[1] >> 4));
+        if pad_count < 2 {
+            out.push((values[1] << 4) | (values[2] >> 2));
+        }
+        if pad_count < 1 {
+            out.push((values[2] << 6) | values[3]);
+        }
+    }
+
+    Ok(out)
+}
+
+fn decode_char(b: u8) -> crate::Result<u8> {
+    ALPHABET
+        .iter()
+        .position(|&c| c == b)
+        .map(|pos| pos as u8)
+        .ok_or(Box::new(Base64Error::InvalidCharacter(b)) as Error)
+}
+
+#[derive(Debug)]
+pub enum Base64Error {
+    /// The filtered input length was not a multiple of 4
+    InvalidLength(usize),
+
+    /// A byte outside the base64 alphabet (and not padding) was found
+    InvalidCharacter(u8),
+}
+
+impl std::error::Error for Base64Error {}
+
+impl fmt::Display for Base64Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Base64Error::InvalidLength(actual) => write!(
+                f,
+                "Expected base64 length to be a multiple of 4 after stripping whitespace but received {}",
+                actual
+            ),
+            Base64Error::InvalidCharacter(byte) => {
+                write!(f, "Byte {} is not a valid base64 character", byte)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let data = b"This is where your secret message will be!";
+        let encoded = encode(data, DEFAULT_LINE_WIDTH);
+        let decoded = decode(&encoded).unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_encode_known_value() {
+        assert_eq!(encode(b"Man", DEFAULT_LINE_WIDTH), "TWFu");
+        assert_eq!(encode(b"Ma", DEFAULT_LINE_WIDTH), "TWE=");
+        assert_eq!(encode(b"M", DEFAULT_LINE_WIDTH), "TQ==");
+    }
+
+    #[test]
+    fn test_encode_wraps_at_line_width() {
+        let data = vec![0u8; 60];
+        let encoded = encode(&data, 10);
+        let first_line = encoded.split("\r\n").next().unwrap();
+        assert_eq!(first_line.len(), 10);
+        assert!(encoded.contains("\r\n"));
+    }
+
+    #[test]
+    fn test_decode_tolerates_embedded_newlines() {
+        let data = vec![42u8; 100];
+        let encoded = encode(&data, DEFAULT_LINE_WIDTH);
+        assert!(encoded.contains("\r\n"));
+        let decoded = decode(&encoded).unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_decode_invalid_character_fails() {
+        assert!(decode("!!!!").is_err());
+    }
+
+    #[test]
+    fn test_decode_invalid_length_fails() {
+        assert!(decode("TWFu0").is_err());
+    }
+}
+
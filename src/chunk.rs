@@ -0,0 +1,371 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str;
+
+use crc::{Crc, CRC_32_ISO_HDLC};
+
+use crate::base64;
+use crate::chunk_type::ChunkType;
+use crate::encryption;
+use crate::Error;
+
+const CRC_32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+
+#[derive(Debug, Clone)]
+pub struct Chunk {
+    length: u32,
+    chunk_type: ChunkType,
+    data: Vec<u8>,
+    crc: u32,
+}
+
+impl Chunk {
+    pub fn new(chunk_type: ChunkType, data: Vec<u8>) -> Chunk {
+        let crc = Self::compute_crc(&chunk_type, &data);
+        Chunk {
+            length: data.len() as u32,
+            chunk_type,
+            data,
+            crc,
+        }
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    pub fn chunk_type(&self) -> &ChunkType {
+        &self.chunk_type
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn crc(&self) -> u32 {
+        self.crc
+    }
+
+    pub fn data_as_string(&self) -> crate::Result<String> {
+        Ok(str::from_utf8(&self.data)?.to_string())
+    }
+
+    /// Encrypts `plaintext` with a key derived from `passphrase` and stores
+    /// the result as the chunk data, so the chunk holds a confidential,
+    /// tamper-evident secret rather than readable bytes.
+    pub fn new_encrypted(
+        chunk_type: ChunkType,
+        plaintext: &[u8],
+        passphrase: &str,
+    ) -> crate::Result<Chunk> {
+        let data = encryption::encrypt(plaintext, passphrase)?;
+        Ok(Chunk::new(chunk_type, data))
+    }
+
+    /// Reverses [`Chunk::new_encrypted`], failing if the authentication tag
+    /// does not match the given passphrase.
+    pub fn decrypt_data(&self, passphrase: &str) -> crate::Result<Vec<u8>> {
+        encryption::decrypt(&self.data, passphrase)
+    }
+
+    /// Stores `bytes` as RFC 2045 line-wrapped base64 text, so the chunk
+    /// data stays ASCII-safe for tooling that assumes ancillary text
+    /// chunks are printable.
+    pub fn new_text_base64(chunk_type: ChunkType, bytes: &[u8]) -> Chunk {
+        let data = base64::encode(bytes, base64::DEFAULT_LINE_WIDTH).into_bytes();
+        Chunk::new(chunk_type, data)
+    }
+
+    /// Reverses [`Chunk::new_text_base64`].
+    pub fn decoded_base64(&self) -> crate::Result<Vec<u8>> {
+        let text = str::from_utf8(&self.data)?;
+        base64::decode(text)
+    }
+
+    pub fn as_bytes(&self) -> Vec<u8> {
+        self.length
+            .to_be_bytes()
+            .iter()
+            .chain(self.chunk_type.bytes().iter())
+            .chain(self.data.iter())
+            .chain(self.crc.to_be_bytes().iter())
+            .copied()
+            .collect()
+    }
+
+    fn compute_crc(chunk_type: &ChunkType, data: &[u8]) -> u32 {
+        let bytes: Vec<u8> = chunk_type
+            .bytes()
+            .iter()
+            .chain(data.iter())
+            .copied()
+            .collect();
+        CRC_32.checksum(&bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for Chunk {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < 12 {
+            return Err(Box::new(ChunkError::TooShort(bytes.len())));
+        }
+
+        let (length_bytes, rest) = bytes.split_at(4);
+        let length = u32::from_be_bytes([
+            length_bytes[0],
+            length_bytes[1],
+            length_bytes[2],
+            length_bytes[3],
+        ]);
+
+        let (type_bytes, rest) = rest.split_at(4);
+        let chunk_type = ChunkType::try_from([
+            type_bytes[0],
+            type_bytes[1],
+            type_bytes[2],
+            type_bytes[3],
+        ])?;
+
+        if rest.len() != length as usize + 4 {
+            return Err(Box::new(ChunkError::LengthMismatch {
+                declared: length,
+                actual: rest.len().saturating_sub(4) as u32,
+            }));
+        }
+
+        let (data, crc_bytes) = rest.split_at(length as usize);
+        let crc = u32::from_be_bytes([crc_bytes[0], crc_bytes[1], crc_bytes[2], crc_bytes[3]]);
+
+        let expected_crc = Self::compute_crc(&chunk_type, data);
+        if crc != expected_crc {
+            return Err(Box::new(ChunkError::CrcMismatch {
+                expected: expected_crc,
+                actual: crc,
+            }));
+        }
+
+        Ok(Chunk {
+            length,
+            chunk_type,
+            data: data.to_vec(),
+            crc,
+        })
+    }
+}
+
+impl fmt::Display for Chunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chunk_type)
+    }
+}
+
+#[derive(Debug)]
+pub enum ChunkError {
+    /// Fewer than 12 bytes were supplied (length + type + crc is the minimum)
+    TooShort(usize),
+
+    /// The declared length did not match the amount of data actually present
+    LengthMismatch { declared: u32, actual: u32 },
+
+    /// The stored CRC did not match the CRC computed over the type and data
+    CrcMismatch { expected: u32, actual: u32 },
+}
+
+impl std::error::Error for ChunkError {}
+
+impl fmt::Display for ChunkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChunkError::TooShort(actual) => write!(
+                f,
+                "Expected at least 12 bytes but received {} when creating chunk",
+                actual
+            ),
+            ChunkError::LengthMismatch { declared, actual } => write!(
+                f,
+                "Declared chunk length {} did not match actual data length {}",
+                declared, actual
+            ),
+            ChunkError::CrcMismatch { expected, actual } => write!(
+                f,
+                "Expected CRC {} but received {} when creating chunk",
+                expected, actual
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn testing_chunk() -> Chunk {
+        let data_length: u32 = 42;
+        let chunk_type = "RuSt".as_bytes();
+        let message_bytes = "This is where your secret message will be!".as_bytes();
+        let crc: u32 = 2882656334;
+
+        let chunk_data: Vec<u8> = data_length
+            .to_be_bytes()
+            .iter()
+            .chain(chunk_type.iter())
+            .chain(message_bytes.iter())
+            .chain(crc.to_be_bytes().iter())
+            .copied()
+            .collect();
+
+        Chunk::try_from(chunk_data.as_ref()).unwrap()
+    }
+
+    #[test]
+    fn test_new_chunk() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let data = "This is where your secret message will be!"
+            .as_bytes()
+            .to_vec();
+        let chunk = Chunk::new(chunk_type, data);
+        assert_eq!(chunk.length(), 42);
+        assert_eq!(chunk.crc(), 2882656334);
+    }
+
+    #[test]
+    fn test_chunk_length() {
+        let chunk = testing_chunk();
+        assert_eq!(chunk.length(), 42);
+    }
+
+    #[test]
+    fn test_chunk_type() {
+        let chunk = testing_chunk();
+        assert_eq!(chunk.chunk_type().to_string(), String::from("RuSt"));
+    }
+
+    #[test]
+    fn test_chunk_string() {
+        let chunk = testing_chunk();
+        let chunk_string = chunk.data_as_string().unwrap();
+        assert_eq!(chunk_string, String::from("This is where your secret message will be!"));
+    }
+
+    #[test]
+    fn test_chunk_crc() {
+        let chunk = testing_chunk();
+        assert_eq!(chunk.crc(), 2882656334);
+    }
+
+    #[test]
+    fn test_valid_chunk_from_bytes() {
+        let data_length: u32 = 42;
+        let chunk_type = "RuSt".as_bytes();
+        let message_bytes = "This is where your secret message will be!".as_bytes();
+        let crc: u32 = 2882656334;
+
+        let chunk_data: Vec<u8> = data_length
+            .to_be_bytes()
+            .iter()
+            .chain(chunk_type.iter())
+            .chain(message_bytes.iter())
+            .chain(crc.to_be_bytes().iter())
+            .copied()
+            .collect();
+
+        let chunk = Chunk::try_from(chunk_data.as_ref()).unwrap();
+
+        assert_eq!(chunk.length(), 42);
+        assert_eq!(chunk.chunk_type().to_string(), String::from("RuSt"));
+        assert_eq!(
+            chunk.data_as_string().unwrap(),
+            String::from("This is where your secret message will be!")
+        );
+        assert_eq!(chunk.crc(), 2882656334);
+    }
+
+    #[test]
+    fn test_invalid_chunk_from_bytes() {
+        let data_length: u32 = 42;
+        let chunk_type = "RuSt".as_bytes();
+        let message_bytes = "This is where your secret message will be!".as_bytes();
+        let crc: u32 = 2882656333;
+
+        let chunk_data: Vec<u8> = data_length
+            .to_be_bytes()
+            .iter()
+            .chain(chunk_type.iter())
+            .chain(message_bytes.iter())
+            .chain(crc.to_be_bytes().iter())
+            .copied()
+            .collect();
+
+        let chunk = Chunk::try_from(chunk_data.as_ref());
+
+        assert!(chunk.is_err());
+    }
+
+    #[test]
+    fn test_chunk_too_short() {
+        let chunk = Chunk::try_from([0u8; 8].as_ref());
+        assert!(chunk.is_err());
+    }
+
+    #[test]
+    fn test_chunk_trait_impls() {
+        let data_length: u32 = 42;
+        let chunk_type = "RuSt".as_bytes();
+        let message_bytes = "This is where your secret message will be!".as_bytes();
+        let crc: u32 = 2882656334;
+
+        let chunk_data: Vec<u8> = data_length
+            .to_be_bytes()
+            .iter()
+            .chain(chunk_type.iter())
+            .chain(message_bytes.iter())
+            .chain(crc.to_be_bytes().iter())
+            .copied()
+            .collect();
+
+        let chunk: Chunk = TryFrom::try_from(chunk_data.as_ref()).unwrap();
+
+        let _chunk_string = format!("{}", chunk);
+    }
+
+    #[test]
+    fn test_new_encrypted_round_trip() {
+        let chunk_type = ChunkType::from_str("seCr").unwrap();
+        let plaintext = b"This is where your secret message will be!";
+        let chunk = Chunk::new_encrypted(chunk_type, plaintext, "hunter2").unwrap();
+
+        assert_ne!(chunk.data(), plaintext);
+        assert_eq!(chunk.decrypt_data("hunter2").unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_decrypt_data_wrong_passphrase_fails() {
+        let chunk_type = ChunkType::from_str("seCr").unwrap();
+        let plaintext = b"This is where your secret message will be!";
+        let chunk = Chunk::new_encrypted(chunk_type, plaintext, "hunter2").unwrap();
+
+        assert!(chunk.decrypt_data("wrong").is_err());
+    }
+
+    #[test]
+    fn test_new_text_base64_round_trip() {
+        let chunk_type = ChunkType::from_str("teXt").unwrap();
+        let bytes = b"This is where your secret message will be!";
+        let chunk = Chunk::new_text_base64(chunk_type, bytes);
+
+        assert!(chunk.data().is_ascii());
+        assert_eq!(chunk.decoded_base64().unwrap(), bytes);
+    }
+
+    #[test]
+    pub fn test_chunk_as_bytes() {
+        let chunk = testing_chunk();
+        assert_eq!(chunk.as_bytes(), chunk.as_bytes());
+        let round_tripped = Chunk::try_from(chunk.as_bytes().as_ref()).unwrap();
+        assert_eq!(chunk.crc(), round_tripped.crc());
+    }
+}
+
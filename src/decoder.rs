@@ -0,0 +1,326 @@
+use std::fmt;
+
+use crc::{Crc, CRC_32_ISO_HDLC};
+
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+
+const CRC_32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+const SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum State {
+    Signature,
+    Length,
+    Type,
+    Data,
+    Crc,
+}
+
+/// Feeds a PNG byte stream through an explicit state machine, emitting
+/// completed, CRC-verified [`Chunk`]s as soon as they are available so
+/// large files can be decoded without holding the whole thing in memory.
+///
+/// The declared length of a chunk is checked against `max_chunk_length`
+/// before any data buffer is allocated, so a hostile or corrupt stream
+/// declaring a huge chunk cannot trigger a huge allocation.
+///
+/// Decoding does not stop at `IEND`: [`Png`](crate::Png) treats everything
+/// up to the end of the buffer as part of the file, including chunks
+/// hidden after `IEND`, so this decoder keeps reading chunks for as long
+/// as it is fed bytes to keep the two parse paths in agreement.
+pub struct ChunkDecoder {
+    state: State,
+    max_chunk_length: u32,
+    scratch: Vec<u8>,
+    declared_length: u32,
+    chunk_type: Option<ChunkType>,
+    data: Vec<u8>,
+}
+
+impl ChunkDecoder {
+    pub fn new(max_chunk_length: u32) -> ChunkDecoder {
+        ChunkDecoder {
+            state: State::Signature,
+            max_chunk_length,
+            scratch: Vec::with_capacity(8),
+            declared_length: 0,
+            chunk_type: None,
+            data: Vec::new(),
+        }
+    }
+
+    /// Feeds `buf` into the decoder, returning every [`Chunk`] that was
+    /// fully decoded as a result. Partial fields are retained across
+    /// calls, so `buf` may be an arbitrarily small slice of the stream.
+    pub fn decode(&mut self, buf: &[u8]) -> crate::Result<Vec<Chunk>> {
+        let mut input = buf;
+        let mut chunks = Vec::new();
+
+        while !input.is_empty() {
+            match self.state {
+                State::Signature => {
+                    if !Self::fill(&mut self.scratch, &mut input, SIGNATURE.len()) {
+                        break;
+                    }
+                    if self.scratch != SIGNATURE {
+                        return Err(Box::new(DecoderError::InvalidSignature(
+                            self.scratch.clone(),
+                        )));
+                    }
+                    self.scratch.clear();
+                    self.state = State::Length;
+                }
+                State::Length => {
+                    if !Self::fill(&mut self.scratch, &mut input, 4) {
+                        break;
+                    }
+                    let length = u32::from_be_bytes([
+                        self.scratch[0],
+                        self.scratch[1],
+                        self.scratch[2],
+                        self.scratch[3],
+                    ]);
+                    if length > self.max_chunk_length {
+                        return Err(Box::new(DecoderError::ChunkTooLarge {
+                            declared: length,
+                            max: self.max_chunk_length,
+                        }));
+                    }
+                    self.declared_length = length;
+                    self.scratch.clear();
+                    self.state = State::Type;
+                }
+                State::Type => {
+                    if !Self::fill(&mut self.scratch, &mut input, 4) {
+                        break;
+                    }
+                    self.chunk_type = Some(ChunkType::try_from([
+                        self.scratch[0],
+                        self.scratch[1],
+                        self.scratch[2],
+                        self.scratch[3],
+                    ])?);
+                    self.scratch.clear();
+                    self.data = Vec::with_capacity(self.declared_length as usize);
+                    self.state = State::Data;
+                }
+                State::Data => {
+                    if !Self::fill(&mut self.data, &mut input, self.declared_length as usize)
+                    {
+                        break;
+                    }
+                    self.state = State::Crc;
+                }
+                State::Crc => {
+                    if !Self::fill(&mut self.scratch, &mut input, 4) {
+                        break;
+                    }
+                    let crc = u32::from_be_bytes([
+                        self.scratch[0],
+                        self.scratch[1],
+                        self.scratch[2],
+                        self.scratch[3],
+                    ]);
+                    let chunk_type = self.chunk_type.take().unwrap();
+                    let expected = CRC_32.checksum(
+                        &chunk_type
+                            .bytes()
+                            .iter()
+                            .chain(self.data.iter())
+                            .copied()
+                            .collect::<Vec<u8>>(),
+                    );
+                    if crc != expected {
+                        return Err(Box::new(DecoderError::CrcMismatch {
+                            expected,
+                            actual: crc,
+                        }));
+                    }
+
+                    chunks.push(Chunk::new(chunk_type, std::mem::take(&mut self.data)));
+
+                    self.scratch.clear();
+                    self.state = State::Length;
+                }
+            }
+        }
+
+        Ok(chunks)
+    }
+
+    /// Copies bytes from `input` onto the end of `target` until it holds
+    /// `need` bytes, advancing `input` past whatever was consumed.
+    /// Returns `true` once `target` has reached `need` bytes.
+    fn fill(target: &mut Vec<u8>, input: &mut &[u8], need: usize) -> bool {
+        let missing = need - target.len();
+        let take = missing.min(input.len());
+        target.extend_from_slice(&input[..take]);
+        *input = &input[take..];
+        target.len() == need
+    }
+}
+
+#[derive(Debug)]
+pub enum DecoderError {
+    /// The first 8 bytes did not match the PNG signature
+    InvalidSignature(Vec<u8>),
+
+    /// A declared chunk length exceeded the configured maximum
+    ChunkTooLarge { declared: u32, max: u32 },
+
+    /// The stored CRC did not match the CRC computed over the type and data
+    CrcMismatch { expected: u32, actual: u32 },
+}
+
+impl std::error::Error for DecoderError {}
+
+impl fmt::Display for DecoderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecoderError::InvalidSignature(actual) => {
+                write!(f, "Expected PNG signature but received {:?}", actual)
+            }
+            DecoderError::ChunkTooLarge { declared, max } => write!(
+                f,
+                "Declared chunk length {} exceeds the maximum of {}",
+                declared, max
+            ),
+            DecoderError::CrcMismatch { expected, actual } => write!(
+                f,
+                "Expected CRC {} but received {} when decoding chunk",
+                expected, actual
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn sample_stream() -> Vec<u8> {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let data = b"This is where your secret message will be!".to_vec();
+        let chunk = Chunk::new(chunk_type, data);
+
+        SIGNATURE
+            .iter()
+            .chain(chunk.as_bytes().iter())
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_whole_stream_at_once() {
+        let stream = sample_stream();
+        let mut decoder = ChunkDecoder::new(1024);
+        let chunks = decoder.decode(&stream).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].chunk_type().to_string(), "RuSt");
+    }
+
+    #[test]
+    fn test_decode_byte_at_a_time() {
+        let stream = sample_stream();
+        let mut decoder = ChunkDecoder::new(1024);
+        let mut chunks = Vec::new();
+        for byte in &stream {
+            chunks.extend(decoder.decode(std::slice::from_ref(byte)).unwrap());
+        }
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(
+            chunks[0].data_as_string().unwrap(),
+            "This is where your secret message will be!"
+        );
+    }
+
+    #[test]
+    fn test_decode_multiple_chunks_across_calls() {
+        let chunk_type = ChunkType::from_str("FrSt").unwrap();
+        let first = Chunk::new(chunk_type, b"first".to_vec());
+        let chunk_type = ChunkType::from_str("LASt").unwrap();
+        let second = Chunk::new(chunk_type, b"second".to_vec());
+
+        let stream: Vec<u8> = SIGNATURE
+            .iter()
+            .chain(first.as_bytes().iter())
+            .chain(second.as_bytes().iter())
+            .copied()
+            .collect();
+
+        let mut decoder = ChunkDecoder::new(1024);
+        let (head, tail) = stream.split_at(stream.len() / 2);
+        let mut chunks = decoder.decode(head).unwrap();
+        chunks.extend(decoder.decode(tail).unwrap());
+
+        assert_eq!(chunks.len(), 2);
+    }
+
+    #[test]
+    fn test_rejects_oversized_declared_length() {
+        let mut stream = SIGNATURE.to_vec();
+        stream.extend_from_slice(&(u32::MAX).to_be_bytes());
+
+        let mut decoder = ChunkDecoder::new(1024);
+        let result = decoder.decode(&stream);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_non_utf8_chunk_type_does_not_panic() {
+        use std::convert::TryFrom;
+
+        let chunk_type = ChunkType::try_from([0xFF, 0xFE, 0xFD, 0xFC]).unwrap();
+        let chunk = Chunk::new(chunk_type, b"hostile".to_vec());
+
+        let stream: Vec<u8> = SIGNATURE
+            .iter()
+            .chain(chunk.as_bytes().iter())
+            .copied()
+            .collect();
+
+        let mut decoder = ChunkDecoder::new(1024);
+        let chunks = decoder.decode(&stream).unwrap();
+        assert_eq!(chunks.len(), 1);
+    }
+
+    #[test]
+    fn test_decodes_chunks_hidden_after_iend() {
+        let iend = Chunk::new(ChunkType::from_str("IEND").unwrap(), Vec::new());
+        let secret = Chunk::new(ChunkType::from_str("seCr").unwrap(), b"hidden".to_vec());
+
+        let stream: Vec<u8> = SIGNATURE
+            .iter()
+            .chain(iend.as_bytes().iter())
+            .chain(secret.as_bytes().iter())
+            .copied()
+            .collect();
+
+        let mut decoder = ChunkDecoder::new(1024);
+        let chunks = decoder.decode(&stream).unwrap();
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].chunk_type().to_string(), "seCr");
+    }
+
+    #[test]
+    fn test_rejects_invalid_signature() {
+        let mut decoder = ChunkDecoder::new(1024);
+        let result = decoder.decode(&[0, 1, 2, 3, 4, 5, 6, 7]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_rejects_crc_mismatch() {
+        let mut stream = sample_stream();
+        let last = stream.len() - 1;
+        stream[last] ^= 0xFF;
+
+        let mut decoder = ChunkDecoder::new(1024);
+        let result = decoder.decode(&stream);
+        assert!(result.is_err());
+    }
+}
+
@@ -0,0 +1,140 @@
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::Argon2;
+use rand::RngCore;
+use std::fmt;
+
+use crate::Error;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Derives a 256-bit key from `passphrase` and `salt` using Argon2.
+fn derive_key(passphrase: &str, salt: &[u8]) -> crate::Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| Box::new(EncryptionError::KeyDerivation(e.to_string())))?;
+    Ok(key)
+}
+
+/// Encrypts `plaintext` with a key derived from `passphrase`, returning
+/// `salt || nonce || ciphertext || tag`.
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> crate::Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+
+    let key_bytes = derive_key(passphrase, &salt)?;
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key_bytes));
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|_| Box::new(EncryptionError::Encrypt))?;
+
+    Ok(salt
+        .iter()
+        .chain(nonce_bytes.iter())
+        .chain(ciphertext.iter())
+        .copied()
+        .collect())
+}
+
+/// Reverses [`encrypt`], failing loudly if the authentication tag does
+/// not match (i.e. the data was tampered with or the passphrase is wrong).
+pub fn decrypt(data: &[u8], passphrase: &str) -> crate::Result<Vec<u8>> {
+    if data.len() < SALT_LEN + NONCE_LEN {
+        return Err(Box::new(EncryptionError::TooShort(data.len())));
+    }
+
+    let (salt, rest) = data.split_at(SALT_LEN);
+    let (nonce_bytes, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let key_bytes = derive_key(passphrase, salt)?;
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key_bytes));
+    let nonce = Nonce::from_slice(nonce_bytes);
+
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| Box::new(EncryptionError::Decrypt) as Error)
+}
+
+#[derive(Debug)]
+pub enum EncryptionError {
+    /// Fewer bytes than a salt and nonce were supplied
+    TooShort(usize),
+
+    /// Argon2 key derivation failed
+    KeyDerivation(String),
+
+    /// AEAD encryption failed
+    Encrypt,
+
+    /// Authentication tag did not match (wrong passphrase or tampering)
+    Decrypt,
+}
+
+impl std::error::Error for EncryptionError {}
+
+impl fmt::Display for EncryptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncryptionError::TooShort(actual) => write!(
+                f,
+                "Expected at least {} bytes of salt and nonce but received {}",
+                SALT_LEN + NONCE_LEN,
+                actual
+            ),
+            EncryptionError::KeyDerivation(reason) => {
+                write!(f, "Failed to derive encryption key: {}", reason)
+            }
+            EncryptionError::Encrypt => write!(f, "Failed to encrypt chunk data"),
+            EncryptionError::Decrypt => write!(
+                f,
+                "Failed to decrypt chunk data: authentication tag mismatch"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        let plaintext = b"a secret message hidden in a png";
+        let encrypted = encrypt(plaintext, "correct horse battery staple").unwrap();
+        let decrypted = decrypt(&encrypted, "correct horse battery staple").unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_decrypt_wrong_passphrase_fails() {
+        let plaintext = b"a secret message hidden in a png";
+        let encrypted = encrypt(plaintext, "correct horse battery staple").unwrap();
+        let result = decrypt(&encrypted, "wrong passphrase");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_decrypt_tampered_ciphertext_fails() {
+        let plaintext = b"a secret message hidden in a png";
+        let mut encrypted = encrypt(plaintext, "correct horse battery staple").unwrap();
+        let last = encrypted.len() - 1;
+        encrypted[last] ^= 0xFF;
+        let result = decrypt(&encrypted, "correct horse battery staple");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_encrypt_output_is_not_plaintext() {
+        let plaintext = b"a secret message hidden in a png";
+        let encrypted = encrypt(plaintext, "correct horse battery staple").unwrap();
+        assert_ne!(encrypted, plaintext.to_vec());
+    }
+}
+
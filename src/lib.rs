@@ -0,0 +1,14 @@
+mod base64;
+mod chunk;
+mod chunk_type;
+mod decoder;
+mod encryption;
+mod png;
+
+pub use chunk::Chunk;
+pub use chunk_type::ChunkType;
+pub use decoder::ChunkDecoder;
+pub use png::Png;
+
+pub type Error = Box<dyn std::error::Error>;
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,404 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::chunk::Chunk;
+use crate::Error;
+
+const STANDARD_HEADER: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+pub struct Png {
+    chunks: Vec<Chunk>,
+}
+
+/// Compares a chunk's raw type bytes against `type_str` without going
+/// through `ChunkType`'s `Display`, which panics on non-UTF-8 type bytes.
+/// Chunk types are attacker-controlled, so this must stay a byte compare.
+fn chunk_type_is(chunk: &Chunk, type_str: &str) -> bool {
+    chunk.chunk_type().bytes().as_slice() == type_str.as_bytes()
+}
+
+impl Png {
+    pub fn from_chunks(chunks: Vec<Chunk>) -> Png {
+        Png { chunks }
+    }
+
+    pub fn append_chunk(&mut self, chunk: Chunk) {
+        self.chunks.push(chunk);
+    }
+
+    pub fn remove_first_chunk(&mut self, chunk_type: &str) -> crate::Result<Chunk> {
+        let index = self
+            .chunks
+            .iter()
+            .position(|chunk| chunk_type_is(chunk, chunk_type))
+            .ok_or_else(|| Box::new(PngError::ChunkNotFound(chunk_type.to_string())))?;
+
+        Ok(self.chunks.remove(index))
+    }
+
+    pub fn header(&self) -> &[u8; 8] {
+        &STANDARD_HEADER
+    }
+
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    pub fn chunk_by_type(&self, chunk_type: &str) -> Option<&Chunk> {
+        self.chunks.iter().find(|chunk| chunk_type_is(chunk, chunk_type))
+    }
+
+    /// Warns about chunks placed somewhere a standard PNG decoder would
+    /// reject, e.g. a custom/secret chunk before `IHDR` or after `IEND`,
+    /// using the critical and safe-to-copy bits already exposed on
+    /// [`ChunkType`](crate::ChunkType) to flag the two ways a hidden chunk can make a file
+    /// non-conformant regardless of its index: being marked critical
+    /// (standard decoders must reject an unrecognized critical chunk) or
+    /// being marked unsafe-to-copy (editors are allowed to drop it, along
+    /// with whatever it hides, when they rewrite the file).
+    pub fn position_warnings(&self) -> Vec<String> {
+        let ihdr_index = self
+            .chunks
+            .iter()
+            .position(|chunk| chunk_type_is(chunk, "IHDR"));
+        let iend_index = self
+            .chunks
+            .iter()
+            .position(|chunk| chunk_type_is(chunk, "IEND"));
+
+        let mut warnings = Vec::new();
+        for (index, chunk) in self.chunks.iter().enumerate() {
+            if chunk_type_is(chunk, "IHDR") || chunk_type_is(chunk, "IEND") {
+                continue;
+            }
+            let chunk_type = chunk.chunk_type();
+            let type_string = String::from_utf8_lossy(&chunk_type.bytes()).into_owned();
+            if let Some(ihdr_index) = ihdr_index {
+                if index < ihdr_index {
+                    warnings.push(format!(
+                        "chunk {} appears before IHDR, which standard decoders require first",
+                        type_string
+                    ));
+                }
+            }
+            if let Some(iend_index) = iend_index {
+                if index > iend_index {
+                    warnings.push(format!(
+                        "chunk {} appears after IEND, which standard decoders treat as the end of the file",
+                        type_string
+                    ));
+                }
+            }
+            if chunk_type.known_description().is_none() {
+                if chunk_type.is_critical() {
+                    warnings.push(format!(
+                        "chunk {} is marked critical but is not a standard chunk type; standard decoders are required to reject a file with an unrecognized critical chunk",
+                        type_string
+                    ));
+                } else if !chunk_type.is_safe_to_copy() {
+                    warnings.push(format!(
+                        "chunk {} is marked unsafe-to-copy; editors that don't recognize it may drop it, along with whatever it hides, when they rewrite the file",
+                        type_string
+                    ));
+                }
+            }
+        }
+        warnings
+    }
+
+    pub fn as_bytes(&self) -> Vec<u8> {
+        STANDARD_HEADER
+            .iter()
+            .chain(self.chunks.iter().flat_map(|chunk| chunk.as_bytes()).collect::<Vec<u8>>().iter())
+            .copied()
+            .collect()
+    }
+}
+
+impl TryFrom<&[u8]> for Png {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < STANDARD_HEADER.len() {
+            return Err(Box::new(PngError::TooShort(bytes.len())));
+        }
+
+        let (header, mut rest) = bytes.split_at(STANDARD_HEADER.len());
+        if header != STANDARD_HEADER {
+            return Err(Box::new(PngError::InvalidHeader(header.to_vec())));
+        }
+
+        let mut chunks = Vec::new();
+        while !rest.is_empty() {
+            if rest.len() < 8 {
+                return Err(Box::new(PngError::TrailingBytes(rest.len())));
+            }
+
+            let declared_length =
+                u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+            let chunk_end = 12 + declared_length;
+            if rest.len() < chunk_end {
+                return Err(Box::new(PngError::TrailingBytes(rest.len())));
+            }
+
+            let (chunk_bytes, remainder) = rest.split_at(chunk_end);
+            chunks.push(Chunk::try_from(chunk_bytes)?);
+            rest = remainder;
+        }
+
+        Ok(Png { chunks })
+    }
+}
+
+impl fmt::Display for Png {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Png {{",)?;
+        writeln!(f, "  Header: {:?}", self.header())?;
+        writeln!(f, "  Chunks: {}", self.chunks.len())?;
+        writeln!(f, "}}")
+    }
+}
+
+#[derive(Debug)]
+pub enum PngError {
+    /// Fewer bytes than the 8-byte signature were supplied
+    TooShort(usize),
+
+    /// The first 8 bytes did not match the PNG signature
+    InvalidHeader(Vec<u8>),
+
+    /// A partial chunk was left over at the end of the buffer
+    TrailingBytes(usize),
+
+    /// No chunk with the requested type was present
+    ChunkNotFound(String),
+}
+
+impl std::error::Error for PngError {}
+
+impl fmt::Display for PngError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PngError::TooShort(actual) => {
+                write!(f, "Expected at least 8 bytes but received {}", actual)
+            }
+            PngError::InvalidHeader(actual) => {
+                write!(f, "Expected PNG signature but received {:?}", actual)
+            }
+            PngError::TrailingBytes(actual) => write!(
+                f,
+                "{} trailing bytes were not enough to form a complete chunk",
+                actual
+            ),
+            PngError::ChunkNotFound(chunk_type) => {
+                write!(f, "No chunk of type {} was found", chunk_type)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::ChunkType;
+    use std::str::FromStr;
+
+    fn chunk_from_strings(chunk_type: &str, data: &str) -> crate::Result<Chunk> {
+        let chunk_type = ChunkType::from_str(chunk_type)?;
+        let data: Vec<u8> = data.bytes().collect();
+
+        Ok(Chunk::new(chunk_type, data))
+    }
+
+    fn testing_png() -> Png {
+        let chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        Png::try_from(
+            STANDARD_HEADER
+                .iter()
+                .chain(chunk_bytes.iter())
+                .copied()
+                .collect::<Vec<u8>>()
+                .as_ref(),
+        )
+        .unwrap()
+    }
+
+    fn testing_chunks() -> Vec<Chunk> {
+        vec![
+            chunk_from_strings("FrSt", "I am the first chunk").unwrap(),
+            chunk_from_strings("miDl", "I am another chunk").unwrap(),
+            chunk_from_strings("LASt", "I am the last chunk").unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_from_chunks() {
+        let chunks = testing_chunks();
+        let png = Png::from_chunks(chunks);
+        assert_eq!(png.chunks().len(), 3);
+    }
+
+    #[test]
+    fn test_valid_from_bytes() {
+        let png = testing_png();
+        assert_eq!(png.chunks().len(), 3);
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        let mut bytes: Vec<u8> = vec![13, 80, 78, 71, 13, 10, 26, 10];
+        bytes.extend(
+            testing_chunks()
+                .into_iter()
+                .flat_map(|chunk| chunk.as_bytes()),
+        );
+
+        let png = Png::try_from(bytes.as_ref());
+        assert!(png.is_err());
+    }
+
+    #[test]
+    fn test_invalid_chunk() {
+        let mut bytes: Vec<u8> = STANDARD_HEADER.to_vec();
+        bytes.extend(vec![
+            0, 0, 0, 5, 66, 97, 100, 32, 67, 104, 117, 110, 107, 0, 0, 0, 0,
+        ]);
+
+        let png = Png::try_from(bytes.as_ref());
+        assert!(png.is_err());
+    }
+
+    #[test]
+    fn test_list_chunks() {
+        let png = testing_png();
+        let chunks = png.chunks();
+        assert_eq!(chunks.len(), 3);
+    }
+
+    #[test]
+    fn test_chunk_by_type() {
+        let png = testing_png();
+        let chunk = png.chunk_by_type("FrSt").unwrap();
+        assert_eq!(chunk.data_as_string().unwrap(), "I am the first chunk");
+    }
+
+    #[test]
+    fn test_append_chunk() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("TeSt", "Message").unwrap());
+        let chunk = png.chunk_by_type("TeSt").unwrap();
+        assert_eq!(chunk.data_as_string().unwrap(), "Message");
+    }
+
+    #[test]
+    fn test_remove_chunk() {
+        let mut png = testing_png();
+        png.remove_first_chunk("FrSt").unwrap();
+        let chunk = png.chunk_by_type("FrSt");
+        assert!(chunk.is_none());
+    }
+
+    #[test]
+    fn test_remove_missing_chunk() {
+        let mut png = testing_png();
+        let result = png.remove_first_chunk("NoNo");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_png_trait_impls() {
+        let chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        let bytes: Vec<u8> = STANDARD_HEADER
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect();
+
+        let png: Png = TryFrom::try_from(bytes.as_ref()).unwrap();
+
+        let _png_string = format!("{}", png);
+    }
+
+    #[test]
+    fn test_position_warnings_for_well_formed_png() {
+        let chunks = vec![
+            chunk_from_strings("IHDR", "header").unwrap(),
+            chunk_from_strings("seCr", "hidden message").unwrap(),
+            chunk_from_strings("IEND", "").unwrap(),
+        ];
+        let png = Png::from_chunks(chunks);
+        assert!(png.position_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_position_warnings_for_misplaced_chunk() {
+        let chunks = vec![
+            chunk_from_strings("seCr", "hidden message").unwrap(),
+            chunk_from_strings("IHDR", "header").unwrap(),
+            chunk_from_strings("IEND", "").unwrap(),
+            chunk_from_strings("laTe", "too late").unwrap(),
+        ];
+        let png = Png::from_chunks(chunks);
+        let warnings = png.position_warnings();
+        assert_eq!(warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_position_warnings_flags_unrecognized_critical_chunk() {
+        let chunks = vec![
+            chunk_from_strings("IHDR", "header").unwrap(),
+            chunk_from_strings("SeCr", "hidden message").unwrap(),
+            chunk_from_strings("IEND", "").unwrap(),
+        ];
+        let png = Png::from_chunks(chunks);
+        let warnings = png.position_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("critical"));
+    }
+
+    #[test]
+    fn test_position_warnings_flags_unsafe_to_copy_chunk() {
+        let chunks = vec![
+            chunk_from_strings("IHDR", "header").unwrap(),
+            chunk_from_strings("seCR", "hidden message").unwrap(),
+            chunk_from_strings("IEND", "").unwrap(),
+        ];
+        let png = Png::from_chunks(chunks);
+        let warnings = png.position_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("unsafe-to-copy"));
+    }
+
+    #[test]
+    fn test_non_utf8_chunk_type_does_not_panic() {
+        let hostile_type = ChunkType::try_from([0xFF, 0xFE, 0xFD, 0xFC]).unwrap();
+        let chunks = vec![
+            chunk_from_strings("IHDR", "header").unwrap(),
+            Chunk::new(hostile_type, b"hostile".to_vec()),
+            chunk_from_strings("IEND", "").unwrap(),
+        ];
+        let png = Png::from_chunks(chunks);
+
+        assert!(png.chunk_by_type("IEND").is_some());
+        let warnings = png.position_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("unsafe-to-copy"));
+    }
+
+    #[test]
+    fn test_png_as_bytes_round_trip() {
+        let png = testing_png();
+        let bytes = png.as_bytes();
+        let round_tripped = Png::try_from(bytes.as_ref()).unwrap();
+        assert_eq!(png.chunks().len(), round_tripped.chunks().len());
+    }
+}
+